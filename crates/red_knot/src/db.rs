@@ -0,0 +1,15 @@
+use red_knot_module_resolver::Db as ResolverDb;
+use red_knot_python_semantic::Db as SemanticDb;
+use ruff_db::Db as SourceDb;
+
+#[salsa::jar(db=Db)]
+pub struct Jar();
+
+/// The red_knot database, composing the source, resolver, and semantic jars.
+pub trait Db: SourceDb + ResolverDb + SemanticDb {
+    /// Unwinds the current query with [`salsa::Cancelled`] if a new change is pending; call this
+    /// periodically from a long-running query's hottest loop.
+    fn check_canceled(&self) {
+        self.unwind_if_cancelled();
+    }
+}
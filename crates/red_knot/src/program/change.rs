@@ -0,0 +1,51 @@
+use ruff_db::file_system::FileSystemPathBuf;
+
+use super::FileChangeKind;
+
+/// A batch of file and search-path root edits to hand to [`Program::apply_change`](super::Program::apply_change) together.
+/// Workspace settings other than search-path roots can't be recorded in a `Change`.
+#[derive(Debug, Default)]
+pub struct Change {
+    pub(super) files: Vec<FileChange>,
+    pub(super) roots_added: Vec<FileSystemPathBuf>,
+    pub(super) roots_removed: Vec<FileSystemPathBuf>,
+}
+
+impl Change {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that the file at `path` was created or modified, optionally providing its new
+    /// text so the database doesn't need to re-read it from disk.
+    pub fn file_changed(&mut self, path: FileSystemPathBuf, kind: FileChangeKind, content: Option<String>) {
+        debug_assert_ne!(kind, FileChangeKind::Deleted, "use `Change::file_deleted` instead");
+        self.files.push(FileChange { path, kind, content });
+    }
+
+    /// Record that the file at `path` was deleted.
+    pub fn file_deleted(&mut self, path: FileSystemPathBuf) {
+        self.files.push(FileChange {
+            path,
+            kind: FileChangeKind::Deleted,
+            content: None,
+        });
+    }
+
+    /// Record that `path` was added as a search-path root.
+    pub fn root_added(&mut self, path: FileSystemPathBuf) {
+        self.roots_added.push(path);
+    }
+
+    /// Record that `path` was removed as a search-path root.
+    pub fn root_removed(&mut self, path: FileSystemPathBuf) {
+        self.roots_removed.push(path);
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct FileChange {
+    pub(super) path: FileSystemPathBuf,
+    pub(super) kind: FileChangeKind,
+    pub(super) content: Option<String>,
+}
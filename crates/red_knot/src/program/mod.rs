@@ -12,7 +12,14 @@ use ruff_db::{Db as SourceDb, Jar as SourceJar, Upcast};
 use crate::db::{Db, Jar};
 use crate::Workspace;
 
+mod change;
 mod check;
+mod package_graph;
+#[cfg(test)]
+mod tests;
+
+pub use change::Change;
+pub use package_graph::{PackageData, PackageGraph, PackageGraphInput, PackageId, PythonVersion, TargetOs};
 
 #[salsa::db(SourceJar, ResolverJar, SemanticJar, Jar)]
 pub struct Program {
@@ -20,6 +27,10 @@ pub struct Program {
     vfs: Vfs,
     fs: Arc<dyn FileSystem + Send + Sync + RefUnwindSafe>,
     workspace: Workspace,
+    // `Option` only because the input has to be created *after* `self` exists (it needs a `&mut
+    // dyn Db` to allocate in Salsa storage); it's `Some` for the entire lifetime of a `Program`
+    // past `new`.
+    package_graph: Option<PackageGraphInput>,
 }
 
 impl Program {
@@ -27,20 +38,72 @@ impl Program {
     where
         Fs: FileSystem + 'static + Send + Sync + RefUnwindSafe,
     {
-        Self {
+        let mut program = Self {
             storage: salsa::Storage::default(),
             vfs: Vfs::default(),
             fs: Arc::new(file_system),
             workspace,
-        }
+            package_graph: None,
+        };
+        program.package_graph = Some(PackageGraphInput::new(&mut program, PackageGraph::new()));
+        program
     }
 
     pub fn apply_changes<I>(&mut self, changes: I)
     where
         I: IntoIterator<Item = FileWatcherChange>,
     {
-        for change in changes {
-            VfsFile::touch_path(self, &VfsPath::file_system(change.path));
+        let mut change = Change::new();
+
+        for watcher_change in changes {
+            change.files.push(change::FileChange {
+                path: watcher_change.path,
+                kind: watcher_change.kind,
+                content: None,
+            });
+        }
+
+        self.apply_change(change);
+    }
+
+    /// Applies a whole [`Change`] to the database: every file and search-path root edit in the
+    /// batch is applied before control returns to the caller, so a multi-file save or a branch
+    /// switch is never observed as a partial edit, even though each file still bumps the Salsa
+    /// revision on its own.
+    pub fn apply_change(&mut self, change: Change) {
+        for file_change in change.files {
+            let vfs_path = VfsPath::file_system(file_change.path);
+
+            match file_change.kind {
+                FileChangeKind::Created | FileChangeKind::Modified => {
+                    // `apply_change` also carries file-watcher events, which fire for
+                    // `site-packages`/typeshed paths just as much as workspace ones, so don't
+                    // force a durability here: a file the resolver already touched keeps the
+                    // durability it was classified with, and a genuinely new file gets
+                    // `touch_path`'s default.
+                    let file = VfsFile::touch_path(self, &vfs_path);
+
+                    if let Some(content) = file_change.content {
+                        // The caller already has the new text (e.g. from an LSP `didChange`), so
+                        // feed it in directly instead of making the next read re-open the file.
+                        file.set_text(self).to(content);
+                    }
+                }
+                FileChangeKind::Deleted => {
+                    // Unlike a create/modify, a deletion can't be satisfied by re-reading the
+                    // path from disk on the next access, so evict the `VfsFile` outright rather
+                    // than merely touching it.
+                    self.vfs.remove(&vfs_path);
+                }
+            }
+        }
+
+        for root in change.roots_added {
+            self.workspace.add_search_path(root);
+        }
+
+        for root in change.roots_removed {
+            self.workspace.remove_search_path(&root);
         }
     }
 
@@ -52,6 +115,37 @@ impl Program {
         &mut self.workspace
     }
 
+    /// The current package graph. Reading this registers the calling query as a dependent of the
+    /// underlying [`PackageGraphInput`], so it re-runs when the graph is replaced.
+    pub fn package_graph(&self) -> &PackageGraph {
+        self.package_graph_input().graph(self)
+    }
+
+    /// Replaces the package graph, bumping the Salsa revision so every query that read it is
+    /// invalidated. Module resolution does not read the package graph, so its results are not
+    /// affected by the graph.
+    pub fn set_package_graph(&mut self, package_graph: PackageGraph) {
+        self.package_graph_input().set_graph(self).to(package_graph);
+    }
+
+    fn package_graph_input(&self) -> PackageGraphInput {
+        self.package_graph
+            .expect("`package_graph` is initialized in `Program::new`")
+    }
+
+    /// The Python version declared by the most specific package whose source root contains
+    /// `path`, if any.
+    ///
+    /// Picks the longest matching root rather than the first, so a package nested inside another
+    /// (e.g. an editable `src/` layout) gets its own version instead of its parent's.
+    pub fn python_version_for_path(&self, path: &FileSystemPathBuf) -> Option<PythonVersion> {
+        self.package_graph()
+            .packages()
+            .filter(|(_, data)| path.starts_with(&data.root))
+            .max_by_key(|(_, data)| data.root.as_str().len())
+            .map(|(_, data)| data.python_version)
+    }
+
     fn with_db<F, T>(&self, f: F) -> Result<T, Cancelled>
     where
         F: FnOnce(&Program) -> T + std::panic::UnwindSafe,
@@ -110,6 +204,7 @@ impl salsa::ParallelDatabase for Program {
             vfs: self.vfs.snapshot(),
             fs: self.fs.clone(),
             workspace: self.workspace.clone(),
+            package_graph: self.package_graph,
         })
     }
 }
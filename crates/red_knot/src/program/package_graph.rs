@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+
+use ruff_db::file_system::FileSystemPathBuf;
+
+/// The target operating system a package is resolved against.
+///
+/// Mirrors the subset of `sys.platform` values that change which platform-specific stub or
+/// typeshed branch applies.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TargetOs {
+    Linux,
+    Darwin,
+    Windows,
+    Unknown,
+}
+
+/// The Python version a package is written against, e.g. its `requires-python` floor.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct PythonVersion {
+    pub major: u8,
+    pub minor: u8,
+}
+
+/// An identifier for a package within a [`PackageGraph`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct PackageId(u32);
+
+/// Metadata describing a single first-party package or dependency.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PackageData {
+    pub name: String,
+    pub root: FileSystemPathBuf,
+    pub python_version: PythonVersion,
+    pub target_os: TargetOs,
+    /// PEP 508 environment markers in effect for this package, e.g. `os_name` or `sys_platform`.
+    pub environment_markers: BTreeMap<String, String>,
+    pub dependencies: Vec<PackageId>,
+}
+
+/// The packages that make up a workspace and their dependency edges.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PackageGraph {
+    packages: BTreeMap<PackageId, PackageData>,
+    next_id: u32,
+}
+
+impl PackageGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `data` to the graph and returns the id it was assigned.
+    pub fn add_package(&mut self, data: PackageData) -> PackageId {
+        let id = PackageId(self.next_id);
+        self.next_id += 1;
+        self.packages.insert(id, data);
+        id
+    }
+
+    pub fn package(&self, id: PackageId) -> &PackageData {
+        &self.packages[&id]
+    }
+
+    /// The direct dependencies declared by `id`.
+    pub fn dependencies(&self, id: PackageId) -> &[PackageId] {
+        &self.packages[&id].dependencies
+    }
+
+    pub fn packages(&self) -> impl Iterator<Item = (PackageId, &PackageData)> {
+        self.packages.iter().map(|(id, data)| (*id, data))
+    }
+}
+
+/// A Salsa input wrapping the [`PackageGraph`], so replacing it through `set_graph` invalidates
+/// every query that read it, the same way editing a `VfsFile`'s text does.
+#[salsa::input]
+pub struct PackageGraphInput {
+    #[return_ref]
+    pub graph: PackageGraph,
+}
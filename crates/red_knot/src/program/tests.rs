@@ -0,0 +1,151 @@
+use std::panic::RefUnwindSafe;
+use std::sync::{Arc, Mutex};
+
+use salsa::DebugWithDb;
+
+use red_knot_module_resolver::{Db as ResolverDb, Jar as ResolverJar};
+use red_knot_python_semantic::{Db as SemanticDb, Jar as SemanticJar};
+use ruff_db::file_system::FileSystem;
+use ruff_db::vfs::Vfs;
+use ruff_db::{Db as SourceDb, Jar as SourceJar, Upcast};
+
+use crate::db::{Db, Jar};
+use crate::Workspace;
+
+/// A [`Db`] that records the [`salsa::Event`]s emitted while recording is enabled, so tests can
+/// assert on query re-execution rather than just on output.
+#[salsa::db(SourceJar, ResolverJar, SemanticJar, Jar)]
+pub(crate) struct TestDb {
+    storage: salsa::Storage<TestDb>,
+    vfs: Vfs,
+    fs: Arc<dyn FileSystem + Send + Sync + RefUnwindSafe>,
+    workspace: Workspace,
+    events: Mutex<Option<Vec<salsa::Event>>>,
+}
+
+impl TestDb {
+    pub(crate) fn new(workspace: Workspace, file_system: impl FileSystem + 'static + Send + Sync + RefUnwindSafe) -> Self {
+        Self {
+            storage: salsa::Storage::default(),
+            vfs: Vfs::default(),
+            fs: Arc::new(file_system),
+            workspace,
+            events: Mutex::new(None),
+        }
+    }
+
+    /// Starts recording [`salsa::Event`]s, discarding any events recorded by a previous session.
+    pub(crate) fn start_recording_events(&self) {
+        *self.events.lock().unwrap() = Some(Vec::new());
+    }
+
+    /// Stops recording and returns the events collected since the last `start_recording_events`.
+    pub(crate) fn stop_recording_events(&self) -> Vec<salsa::Event> {
+        self.events.lock().unwrap().take().unwrap_or_default()
+    }
+
+    /// Counts recorded executions of the query identified by `query_name`, without draining the
+    /// buffer (unlike [`stop_recording_events`](Self::stop_recording_events)).
+    pub(crate) fn count_query_executions(&self, query_name: &str) -> usize {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .flatten()
+            .filter(|event| match event.kind {
+                salsa::EventKind::WillExecute { database_key } => {
+                    format!("{:?}", database_key.debug(self)).contains(query_name)
+                }
+                _ => false,
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ruff_db::file_system::{FileSystemPathBuf, MemoryFileSystem};
+    use ruff_db::vfs::{VfsFile, VfsPath};
+
+    use crate::Workspace;
+
+    use super::TestDb;
+
+    #[test]
+    fn count_query_executions_counts_without_consuming() {
+        let fs = MemoryFileSystem::default();
+        let path = FileSystemPathBuf::from("/src/foo.py");
+        fs.write_file(&path, "x = 1".to_string()).unwrap();
+
+        let mut db = TestDb::new(Workspace::new(FileSystemPathBuf::from("/src")), fs);
+
+        db.start_recording_events();
+
+        let vfs_path = VfsPath::file_system(path);
+        let file = VfsFile::touch_path(&mut db, &vfs_path);
+        file.text(&db);
+
+        let first = db.count_query_executions("file_text");
+        assert!(first > 0, "expected `file_text` to have executed at least once");
+
+        let second = db.count_query_executions("file_text");
+        assert_eq!(
+            first, second,
+            "counting again without resetting must not drain the recorded events"
+        );
+    }
+}
+
+impl Upcast<dyn SemanticDb> for TestDb {
+    fn upcast(&self) -> &(dyn SemanticDb + 'static) {
+        self
+    }
+}
+
+impl Upcast<dyn SourceDb> for TestDb {
+    fn upcast(&self) -> &(dyn SourceDb + 'static) {
+        self
+    }
+}
+
+impl Upcast<dyn ResolverDb> for TestDb {
+    fn upcast(&self) -> &(dyn ResolverDb + 'static) {
+        self
+    }
+}
+
+impl ResolverDb for TestDb {}
+
+impl SemanticDb for TestDb {}
+
+impl SourceDb for TestDb {
+    fn file_system(&self) -> &dyn FileSystem {
+        &*self.fs
+    }
+
+    fn vfs(&self) -> &Vfs {
+        &self.vfs
+    }
+}
+
+impl Db for TestDb {}
+
+impl salsa::Database for TestDb {
+    fn salsa_event(&self, event: salsa::Event) {
+        if let Some(events) = &mut *self.events.lock().unwrap() {
+            events.push(event);
+        }
+    }
+}
+
+impl salsa::ParallelDatabase for TestDb {
+    fn snapshot(&self) -> salsa::Snapshot<Self> {
+        salsa::Snapshot::new(Self {
+            storage: self.storage.snapshot(),
+            vfs: self.vfs.snapshot(),
+            fs: self.fs.clone(),
+            workspace: self.workspace.clone(),
+            events: Mutex::new(None),
+        })
+    }
+}
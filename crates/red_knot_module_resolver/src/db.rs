@@ -0,0 +1,13 @@
+use ruff_db::Db as SourceDb;
+
+#[salsa::jar(db=Db)]
+pub struct Jar();
+
+/// The module resolver's database, layered on top of the source jar.
+pub trait Db: SourceDb {
+    /// Unwinds the current query with [`salsa::Cancelled`] if a new change is pending. Within the
+    /// resolver it is called only by `resolve_namespace_package`, once per candidate directory.
+    fn check_canceled(&self) {
+        self.unwind_if_cancelled();
+    }
+}
@@ -1,7 +1,7 @@
 use std::fmt::Formatter;
 use std::sync::Arc;
 
-use ruff_db::vfs::VfsFile;
+use ruff_db::vfs::{VfsFile, VfsPath};
 
 use crate::db::Db;
 use crate::module_name::ModuleName;
@@ -14,11 +14,17 @@ pub struct Module {
 }
 
 impl Module {
+    /// Constructs a `Module` whose file is already resolved to a [`ModuleFile`].
+    ///
+    /// Prefer [`Module::new_single_file`] when resolving a `.py`/`.pyi` module or a package's
+    /// `__init__`: it creates the `VfsFile` itself so it can set the correct
+    /// [`salsa::Durability`] at the point the file is created, rather than relying on the
+    /// caller to have done so.
     pub(crate) fn new(
         name: ModuleName,
         kind: ModuleKind,
         search_path: Arc<ModuleResolutionPathBuf>,
-        file: VfsFile,
+        file: ModuleFile,
     ) -> Self {
         Self {
             inner: Arc::new(ModuleInner {
@@ -30,14 +36,40 @@ impl Module {
         }
     }
 
+    /// Constructs a `Module` backed by a single file, creating its `VfsFile` with the
+    /// [`salsa::Durability`] appropriate for `search_path`: first-party workspace sources get
+    /// `LOW` durability, vendored typeshed/stdlib stubs get `HIGH`, and installed third-party
+    /// packages get `MEDIUM`. This is what lets editing a workspace file avoid re-verifying the
+    /// huge subgraph of queries rooted in typeshed.
+    pub(crate) fn new_single_file(
+        db: &mut dyn Db,
+        name: ModuleName,
+        kind: ModuleKind,
+        search_path: Arc<ModuleResolutionPathBuf>,
+        path: &VfsPath,
+    ) -> Self {
+        let durability = ModuleResolutionPathRef::from(&*search_path).durability();
+        let file = VfsFile::touch_path_with_durability(db, path, durability);
+
+        Self::new(name, kind, search_path, ModuleFile::Single(file))
+    }
+
     /// The absolute name of the module (e.g. `foo.bar`)
     pub fn name(&self) -> &ModuleName {
         &self.inner.name
     }
 
-    /// The file to the source code that defines this module
-    pub fn file(&self) -> VfsFile {
-        self.inner.file
+    /// The file (or, for a namespace package, the directories) that define this module.
+    pub fn file(&self) -> &ModuleFile {
+        &self.inner.file
+    }
+
+    /// The single file that defines this module, or `None` if it's a namespace package.
+    ///
+    /// Convenience accessor for the common case (a `.py`/`.pyi` module or a regular package),
+    /// so callers that don't care about namespace packages don't have to match on [`file`](Self::file).
+    pub fn single_file(&self) -> Option<VfsFile> {
+        self.inner.file.as_single()
     }
 
     /// The search path from which the module was resolved.
@@ -64,10 +96,15 @@ impl std::fmt::Debug for Module {
 
 impl salsa::DebugWithDb<dyn Db> for Module {
     fn fmt(&self, f: &mut Formatter<'_>, db: &dyn Db) -> std::fmt::Result {
+        let file = match self.file() {
+            ModuleFile::Single(file) => format!("{:?}", file.debug(db.upcast())),
+            ModuleFile::Namespace(dirs) => format!("<namespace package: {dirs:?}>"),
+        };
+
         f.debug_struct("Module")
             .field("name", &self.name())
             .field("kind", &self.kind())
-            .field("file", &self.file().debug(db.upcast()))
+            .field("file", &file)
             .field("search_path", &self.search_path())
             .finish()
     }
@@ -78,7 +115,98 @@ struct ModuleInner {
     name: ModuleName,
     kind: ModuleKind,
     search_path: Arc<ModuleResolutionPathBuf>,
-    file: VfsFile,
+    file: ModuleFile,
+}
+
+/// The file(s) that back a [`Module`].
+///
+/// Most modules are defined by a single `.py`/`.pyi` file (or a regular package's
+/// `__init__.py(i)`). A PEP 420 namespace package has no such file: it's just a directory,
+/// potentially contributed to by more than one search path, so it's represented by the list of
+/// directories that make it up instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ModuleFile {
+    /// The single file that defines this module.
+    Single(VfsFile),
+
+    /// The directories (one per contributing search path) that make up this namespace package.
+    Namespace(Vec<VfsPath>),
+}
+
+impl ModuleFile {
+    /// The single file that defines this module, or `None` if it's a namespace package.
+    pub fn as_single(&self) -> Option<VfsFile> {
+        match self {
+            Self::Single(file) => Some(*file),
+            Self::Namespace(_) => None,
+        }
+    }
+}
+
+impl ModuleResolutionPathRef<'_> {
+    /// The [`salsa::Durability`] appropriate for files resolved from this search path.
+    pub(crate) fn durability(&self) -> salsa::Durability {
+        match self {
+            Self::StandardLibrary(_) => salsa::Durability::HIGH,
+            Self::SitePackages(_) => salsa::Durability::MEDIUM,
+            // `Extra` search paths are user-configured first-party-adjacent source roots, not
+            // vendored stubs, so they change just as often as `FirstParty` ones.
+            Self::Extra(_) | Self::FirstParty(_) => salsa::Durability::LOW,
+        }
+    }
+}
+
+/// Builds a PEP 420 namespace package for `name` from `candidate_directories`, typically one
+/// directory per search path whose last component matched `name`. It does not itself look for a
+/// `.py`/`.pyi` module named `name`; that check is left to the caller.
+///
+/// A directory only contributes to the namespace package if it actually exists and still has no
+/// `__init__.py(i)` (resolution may race a concurrent edit that turns it into a regular package).
+/// Returns `None` if no candidate qualifies, so the caller can fall through to "module not found"
+/// instead of returning an empty namespace package.
+pub(crate) fn resolve_namespace_package(
+    db: &dyn Db,
+    name: ModuleName,
+    search_path: Arc<ModuleResolutionPathBuf>,
+    candidate_directories: impl IntoIterator<Item = VfsPath>,
+) -> Option<Module> {
+    let fs = db.file_system();
+    let mut resolved: Vec<VfsPath> = Vec::new();
+
+    for directory in candidate_directories {
+        db.check_canceled();
+
+        if resolved.contains(&directory) {
+            continue;
+        }
+
+        let Some(fs_path) = directory.as_file_system_path() else {
+            continue;
+        };
+
+        if !fs.is_directory(fs_path) {
+            continue;
+        }
+
+        let has_init = ["__init__.py", "__init__.pyi"]
+            .into_iter()
+            .any(|init| fs.exists(&fs_path.join(init)));
+
+        if !has_init {
+            resolved.push(directory);
+        }
+    }
+
+    if resolved.is_empty() {
+        return None;
+    }
+
+    Some(Module::new(
+        name,
+        ModuleKind::NamespacePackage,
+        search_path,
+        ModuleFile::Namespace(resolved),
+    ))
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -88,4 +216,8 @@ pub enum ModuleKind {
 
     /// A python package (`foo/__init__.py` or `foo/__init__.pyi`)
     Package,
+
+    /// A PEP 420 implicit namespace package: a directory with no `__init__.py(i)`, which may be
+    /// split across multiple search paths.
+    NamespacePackage,
 }